@@ -0,0 +1,297 @@
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+
+use tokio::sync::{OnceCell, RwLock, broadcast, mpsc};
+use tokio_stream::wrappers::BroadcastStream;
+use tonic::Response;
+
+use crate::{
+    api::{Error, Heartbeat, HeartbeatStream, ResponseStream, Services},
+    sys::{
+        capabilities::Capabilities,
+        message::Nlri,
+        session::{Event, Session, SessionConfig},
+    },
+};
+
+mod proto {
+    tonic::include_proto!("bgp");
+}
+
+mod rib {
+    tonic::include_proto!("rib");
+}
+
+pub use proto::bgp_service_server::BgpServiceServer;
+
+const UPDATE_CHANNEL_CAPACITY: usize = 1024;
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+impl From<Services> for proto::Services {
+    fn from(services: Services) -> Self {
+        Self {
+            features: services.bits(),
+            protocol_version: super::services::PROTOCOL_VERSION,
+        }
+    }
+}
+
+impl From<Services> for rib::Services {
+    fn from(services: Services) -> Self {
+        Self {
+            features: services.bits(),
+            protocol_version: super::services::PROTOCOL_VERSION,
+        }
+    }
+}
+
+impl From<rib::Services> for Services {
+    fn from(services: rib::Services) -> Self {
+        Services::from_bits(services.features)
+    }
+}
+
+impl Heartbeat for proto::UpdateEvent {
+    fn nop() -> Self {
+        Self {
+            kind: Some(proto::update_event::Kind::Nop(proto::Nop {})),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BgpService {
+    local_asn: u16,
+    router_id: u32,
+    services: Services,
+    sessions: Arc<RwLock<HashMap<SocketAddr, tokio::task::JoinHandle<()>>>>,
+    updates: broadcast::Sender<proto::UpdateEvent>,
+    rib: rib::rib_service_client::RibServiceClient<tonic::transport::Channel>,
+    rib_services: Arc<OnceCell<Services>>,
+}
+
+impl BgpService {
+    pub fn new(local_asn: u16, router_id: u32, services: Services) -> Self {
+        let (updates, _receiver) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        let rib = rib::rib_service_client::RibServiceClient::new(
+            tonic::transport::Endpoint::from_static("http://[::1]:50052").connect_lazy(),
+        );
+
+        Self {
+            local_asn,
+            router_id,
+            services,
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            updates,
+            rib,
+            rib_services: Arc::new(OnceCell::new()),
+        }
+    }
+
+    fn local_capabilities(&self) -> Capabilities {
+        Capabilities::MULTIPROTOCOL | Capabilities::FOUR_OCTET_ASN | Capabilities::ROUTE_REFRESH
+    }
+}
+
+#[tonic::async_trait]
+impl proto::bgp_service_server::BgpService for BgpService {
+    type WatchUpdatesStream = ResponseStream<proto::UpdateEvent>;
+
+    async fn hello(
+        &self,
+        _request: tonic::Request<proto::HelloRequest>,
+    ) -> tonic::Result<Response<proto::HelloResponse>> {
+        Ok(Response::new(proto::HelloResponse {
+            services: Some(self.services.into()),
+        }))
+    }
+
+    async fn add_neighbor(
+        &self,
+        request: tonic::Request<proto::Neighbor>,
+    ) -> tonic::Result<Response<()>> {
+        if !self.services.supports(Services::BGP) {
+            return Err(tonic::Status::unimplemented(
+                "this node did not advertise BGP",
+            ));
+        }
+
+        let neighbor = request.into_inner();
+        let peer: SocketAddr = format!("{}:{}", neighbor.address, neighbor.port)
+            .parse()
+            .map_err(|_| tonic::Status::invalid_argument("invalid neighbor address"))?;
+        let expected_peer_asn = u16::try_from(neighbor.asn)
+            .map_err(|_| tonic::Status::invalid_argument("asn must fit in 16 bits"))?;
+
+        let session = Session::new(SessionConfig {
+            local_asn: self.local_asn,
+            local_router_id: self.router_id,
+            hold_time: 180,
+            capabilities: self.local_capabilities(),
+            expected_peer_asn: Some(expected_peer_asn),
+        });
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let updates = self.updates.clone();
+        let mut rib = self.rib.clone();
+        let rib_services = self.rib_services.clone();
+        let local_services = self.services;
+
+        tokio::spawn(async move {
+            while let Some(event) = events_rx.recv().await {
+                if let Err(err) =
+                    forward_event(&mut rib, &rib_services, local_services, &updates, event).await
+                {
+                    tracing::error!("failed to forward BGP update: {err}");
+                }
+            }
+        });
+
+        let handle = tokio::spawn(async move {
+            if let Err(err) = session.run(peer, events_tx).await {
+                tracing::error!("BGP session with {peer} ended: {err}");
+            }
+        });
+
+        if let Some(previous) = self.sessions.write().await.insert(peer, handle) {
+            previous.abort();
+        }
+
+        Ok(Response::new(()))
+    }
+
+    async fn remove_neighbor(
+        &self,
+        request: tonic::Request<proto::Neighbor>,
+    ) -> tonic::Result<Response<()>> {
+        let neighbor = request.into_inner();
+        let peer: SocketAddr = format!("{}:{}", neighbor.address, neighbor.port)
+            .parse()
+            .map_err(|_| tonic::Status::invalid_argument("invalid neighbor address"))?;
+
+        if let Some(handle) = self.sessions.write().await.remove(&peer) {
+            handle.abort();
+        }
+
+        Ok(Response::new(()))
+    }
+
+    async fn watch_updates(
+        &self,
+        _request: tonic::Request<()>,
+    ) -> tonic::Result<Response<Self::WatchUpdatesStream>> {
+        use futures::StreamExt;
+
+        let stream = BroadcastStream::new(self.updates.subscribe())
+            .filter_map(|event| async move { event.ok().map(Ok) });
+
+        Ok(Response::new(Box::pin(HeartbeatStream::new(
+            stream,
+            HEARTBEAT_INTERVAL,
+        ))))
+    }
+}
+
+/// Looks up (and caches) the RIB's advertised [`Services`] via its `Hello`
+/// RPC, so we only issue `AddRoute` once we know the peer actually
+/// implements `RIB_WRITE` instead of finding out from a failed call.
+async fn rib_services(
+    rib: &mut rib::rib_service_client::RibServiceClient<tonic::transport::Channel>,
+    cache: &OnceCell<Services>,
+    local_services: Services,
+) -> Services {
+    if let Some(services) = cache.get() {
+        return *services;
+    }
+
+    let services = match rib
+        .hello(rib::HelloRequest {
+            services: Some(local_services.into()),
+        })
+        .await
+    {
+        Ok(response) => response
+            .into_inner()
+            .services
+            .map(Services::from)
+            .unwrap_or_default(),
+        Err(err) => {
+            tracing::warn!("failed to query RIB service's capabilities via Hello: {err}");
+            Services::empty()
+        }
+    };
+
+    *cache.get_or_init(|| async { services }).await
+}
+
+async fn forward_event(
+    rib: &mut rib::rib_service_client::RibServiceClient<tonic::transport::Channel>,
+    rib_services_cache: &OnceCell<Services>,
+    local_services: Services,
+    updates: &broadcast::Sender<proto::UpdateEvent>,
+    event: Event,
+) -> Result<(), Error> {
+    let (update_event, rib_route) = match event {
+        Event::Announce(nlri) => (
+            proto::UpdateEvent {
+                kind: Some(proto::update_event::Kind::Announce(proto::Announce {
+                    nlri: Some(to_proto_nlri(&nlri)),
+                    attributes: None,
+                })),
+            },
+            Some(rib::AddRouteRequest {
+                netns: None,
+                route: Some(nlri_to_rib_route(&nlri)),
+            }),
+        ),
+        Event::Withdraw(nlri) => (
+            proto::UpdateEvent {
+                kind: Some(proto::update_event::Kind::Withdraw(proto::Withdraw {
+                    nlri: Some(to_proto_nlri(&nlri)),
+                })),
+            },
+            None,
+        ),
+    };
+
+    updates.send(update_event).ok();
+
+    if let Some(request) = rib_route {
+        let peer_services = rib_services(rib, rib_services_cache, local_services).await;
+
+        if peer_services.includes(Services::RIB_WRITE) {
+            rib.add_route(request).await?;
+        } else {
+            tracing::warn!("RIB service did not advertise RIB_WRITE, dropping route update");
+        }
+    }
+
+    Ok(())
+}
+
+fn to_proto_nlri(nlri: &Nlri) -> proto::Nlri {
+    proto::Nlri {
+        prefix: nlri.prefix.clone(),
+        prefix_len: nlri.prefix_len as u32,
+    }
+}
+
+fn nlri_to_rib_route(nlri: &Nlri) -> rib::Route {
+    rib::Route {
+        destination: pad_prefix(&nlri.prefix, nlri.prefix_len),
+        prefix_len: nlri.prefix_len as u32,
+        gateway: Vec::new(),
+        oif_index: 0,
+    }
+}
+
+/// `Nlri::prefix` only carries `prefix_len.div_ceil(8)` significant bytes
+/// (e.g. 3 bytes for a /24), but the RIB expects a full 4- or 16-byte
+/// address depending on AFI. Zero-extend to the address width implied by
+/// the prefix length so the RIB's `bytes_to_ip` can parse it.
+fn pad_prefix(prefix: &[u8], prefix_len: u8) -> Vec<u8> {
+    let width = if prefix_len <= 32 { 4 } else { 16 };
+    let mut padded = vec![0u8; width];
+    let len = prefix.len().min(width);
+    padded[..len].copy_from_slice(&prefix[..len]);
+    padded
+}
@@ -0,0 +1,60 @@
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::Stream;
+use tokio::time::{Instant, Interval, MissedTickBehavior, interval_at};
+
+/// A streamed response type that can represent a no-op keepalive frame, so
+/// [`HeartbeatStream`] can synthesize one without needing to know anything
+/// else about the message.
+pub trait Heartbeat: Sized {
+    fn nop() -> Self;
+}
+
+/// Wraps a response stream so that, when no real item arrives within
+/// `interval`, a lightweight no-op message is emitted instead. This lets
+/// long-lived streams such as `watch_updates` keep a half-open connection or a hung server task detectable instead of
+/// staying silent for hours between real events.
+pub struct HeartbeatStream<S, T> {
+    inner: S,
+    ticker: Interval,
+    _marker: PhantomData<T>,
+}
+
+impl<S, T> HeartbeatStream<S, T> {
+    pub fn new(inner: S, interval_duration: Duration) -> Self {
+        let mut ticker = interval_at(Instant::now() + interval_duration, interval_duration);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        Self {
+            inner,
+            ticker,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, T> Stream for HeartbeatStream<S, T>
+where
+    S: Stream<Item = tonic::Result<T>> + Unpin,
+    T: Heartbeat,
+{
+    type Item = tonic::Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Poll::Ready(item) = Pin::new(&mut self.inner).poll_next(cx) {
+            self.ticker.reset();
+            return Poll::Ready(item);
+        }
+
+        if self.ticker.poll_tick(cx).is_ready() {
+            return Poll::Ready(Some(Ok(T::nop())));
+        }
+
+        Poll::Pending
+    }
+}
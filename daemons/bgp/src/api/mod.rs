@@ -0,0 +1,29 @@
+use std::pin::Pin;
+
+use futures::Stream;
+
+mod bgp;
+mod heartbeat;
+mod services;
+
+pub use bgp::{BgpService, BgpServiceServer};
+pub use heartbeat::{Heartbeat, HeartbeatStream};
+pub use services::Services;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Session(#[from] crate::sys::session::Error),
+    #[error(transparent)]
+    Transport(#[from] tonic::transport::Error),
+    #[error("rib service call failed: {0}")]
+    Rib(#[from] tonic::Status),
+}
+
+impl From<Error> for tonic::Status {
+    fn from(err: Error) -> Self {
+        tonic::Status::internal(err.to_string())
+    }
+}
+
+type ResponseStream<T> = Pin<Box<dyn Stream<Item = tonic::Result<T>> + Send>>;
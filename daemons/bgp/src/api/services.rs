@@ -0,0 +1,53 @@
+/// The wire protocol version this build speaks.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The set of RPC surfaces a node supports, as a compact `u64` flag bitset,
+/// exchanged via `BgpService::Hello` before a peer relies on anything
+/// beyond the baseline it already knows the other side supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Services(u64);
+
+impl Services {
+    pub const NETNS_WATCH: Self = Self(1 << 0);
+    pub const RIB_WRITE: Self = Self(1 << 1);
+    pub const BGP: Self = Self(1 << 2);
+    pub const STREAM_HEARTBEAT: Self = Self(1 << 3);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn with_bgp(self, enabled: bool) -> Self {
+        self.with(Self::BGP, enabled)
+    }
+
+    pub fn with_heartbeat(self, enabled: bool) -> Self {
+        self.with(Self::STREAM_HEARTBEAT, enabled)
+    }
+
+    fn with(self, flag: Self, enabled: bool) -> Self {
+        if enabled {
+            Self(self.0 | flag.0)
+        } else {
+            Self(self.0 & !flag.0)
+        }
+    }
+
+    pub fn supports(self, flag: Self) -> bool {
+        self.0 & flag.0 == flag.0
+    }
+
+    /// Whether every feature `other` advertises is also advertised by
+    /// `self`.
+    pub fn includes(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+}
@@ -0,0 +1,40 @@
+mod api;
+mod sys;
+
+use std::{net::SocketAddr, str::FromStr};
+
+use api::Services;
+use tonic::transport;
+use tracing_subscriber::FmtSubscriber;
+
+const LOCAL_ASN: u16 = 65000;
+const LOCAL_ROUTER_ID: u32 = 1;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Transport(#[from] transport::Error),
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing::subscriber::set_global_default(
+        FmtSubscriber::builder()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_env("NETWORKER_BGP_LOG"))
+            .finish(),
+    )
+    .expect("tracing setup failed");
+
+    let services = Services::empty().with_bgp(true).with_heartbeat(true);
+
+    transport::Server::builder()
+        .add_service(api::BgpServiceServer::new(api::BgpService::new(
+            LOCAL_ASN,
+            LOCAL_ROUTER_ID,
+            services,
+        )))
+        .serve(SocketAddr::from_str("[::1]:50053").unwrap())
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use std::ops::{BitOr, BitOrAssign};
+
+/// Optional BGP capabilities advertised in a peer's OPEN message, encoded as
+/// a flag bitset so the effective session capabilities can be computed as a
+/// plain intersection of what both sides advertised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    pub const MULTIPROTOCOL: Self = Self(1 << 0);
+    pub const FOUR_OCTET_ASN: Self = Self(1 << 1);
+    pub const ROUTE_REFRESH: Self = Self(1 << 2);
+    pub const GRACEFUL_RESTART: Self = Self(1 << 3);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn supports(&self, capability: Self) -> bool {
+        self.0 & capability.0 == capability.0
+    }
+
+    /// The capabilities both `self` and `other` advertised, i.e. the set a
+    /// session may actually rely on once negotiation completes.
+    pub fn intersect(&self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Capabilities {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
@@ -0,0 +1,255 @@
+use crate::sys::capabilities::Capabilities;
+
+const MARKER: [u8; 16] = [0xff; 16];
+const HEADER_LEN: usize = 19;
+const OPEN_VERSION: u8 = 4;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("message is truncated")]
+    Truncated,
+    #[error("message length `{0}` is shorter than the {HEADER_LEN} byte BGP header")]
+    InvalidLength(u16),
+    #[error("unknown message type `{0}`")]
+    UnknownType(u8),
+    #[error("unsupported BGP version `{0}`, only version {OPEN_VERSION} is supported")]
+    UnsupportedVersion(u8),
+}
+
+#[derive(Debug, Clone)]
+pub struct Open {
+    pub asn: u16,
+    pub hold_time: u16,
+    pub router_id: u32,
+    pub capabilities: Capabilities,
+}
+
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub withdrawn: Vec<Nlri>,
+    pub announced: Vec<Nlri>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Nlri {
+    pub prefix_len: u8,
+    pub prefix: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Open(Open),
+    Update(Update),
+    Keepalive,
+    Notification { code: u8, subcode: u8 },
+}
+
+impl Message {
+    pub fn encode(&self) -> Vec<u8> {
+        let (kind, body) = match self {
+            Message::Open(open) => (1, encode_open(open)),
+            Message::Update(update) => (2, encode_update(update)),
+            Message::Notification { code, subcode } => (3, vec![*code, *subcode]),
+            Message::Keepalive => (4, Vec::new()),
+        };
+
+        let mut message = Vec::with_capacity(HEADER_LEN + body.len());
+        message.extend_from_slice(&MARKER);
+        message.extend_from_slice(&((HEADER_LEN + body.len()) as u16).to_be_bytes());
+        message.push(kind);
+        message.extend_from_slice(&body);
+
+        message
+    }
+
+    pub fn decode(buffer: &[u8]) -> Result<(Self, usize), Error> {
+        if buffer.len() < HEADER_LEN {
+            return Err(Error::Truncated);
+        }
+
+        let length_field = u16::from_be_bytes([buffer[16], buffer[17]]);
+        let length = length_field as usize;
+        if length < HEADER_LEN {
+            return Err(Error::InvalidLength(length_field));
+        }
+        if buffer.len() < length {
+            return Err(Error::Truncated);
+        }
+
+        let body = &buffer[HEADER_LEN..length];
+        let message = match buffer[18] {
+            1 => Message::Open(decode_open(body)?),
+            2 => Message::Update(decode_update(body)),
+            3 => Message::Notification {
+                code: body.first().copied().unwrap_or_default(),
+                subcode: body.get(1).copied().unwrap_or_default(),
+            },
+            4 => Message::Keepalive,
+            kind => return Err(Error::UnknownType(kind)),
+        };
+
+        Ok((message, length))
+    }
+}
+
+fn encode_open(open: &Open) -> Vec<u8> {
+    let capabilities = encode_capabilities(open.capabilities);
+    let mut body = Vec::with_capacity(10 + capabilities.len());
+
+    body.push(OPEN_VERSION);
+    body.extend_from_slice(&open.asn.to_be_bytes());
+    body.extend_from_slice(&open.hold_time.to_be_bytes());
+    body.extend_from_slice(&open.router_id.to_be_bytes());
+    body.push(capabilities.len() as u8);
+    body.extend_from_slice(&capabilities);
+
+    body
+}
+
+fn decode_open(body: &[u8]) -> Result<Open, Error> {
+    if body.len() < 10 {
+        return Err(Error::Truncated);
+    }
+
+    if body[0] != OPEN_VERSION {
+        return Err(Error::UnsupportedVersion(body[0]));
+    }
+
+    let asn = u16::from_be_bytes([body[1], body[2]]);
+    let hold_time = u16::from_be_bytes([body[3], body[4]]);
+    let router_id = u32::from_be_bytes([body[5], body[6], body[7], body[8]]);
+    let parameters_len = body[9] as usize;
+    let parameters = body.get(10..10 + parameters_len).unwrap_or_default();
+
+    Ok(Open {
+        asn,
+        hold_time,
+        router_id,
+        capabilities: decode_capabilities(parameters),
+    })
+}
+
+/// Each advertised capability is carried as its own optional parameter
+/// (type 2); we only ever emit single-byte capability codes, one per
+/// negotiated [`Capabilities`] flag.
+fn encode_capabilities(capabilities: Capabilities) -> Vec<u8> {
+    const CODES: [(Capabilities, u8); 4] = [
+        (Capabilities::MULTIPROTOCOL, 1),
+        (Capabilities::ROUTE_REFRESH, 2),
+        (Capabilities::FOUR_OCTET_ASN, 65),
+        (Capabilities::GRACEFUL_RESTART, 64),
+    ];
+
+    let mut parameters = Vec::new();
+
+    for (capability, code) in CODES {
+        if capabilities.supports(capability) {
+            parameters.extend_from_slice(&[2, 2, code, 0]);
+        }
+    }
+
+    parameters
+}
+
+fn decode_capabilities(parameters: &[u8]) -> Capabilities {
+    let mut capabilities = Capabilities::empty();
+    let mut offset = 0;
+
+    while offset + 2 <= parameters.len() {
+        let kind = parameters[offset];
+        let len = parameters[offset + 1] as usize;
+        let value = parameters.get(offset + 2..offset + 2 + len).unwrap_or_default();
+
+        if kind == 2 {
+            if let Some(&code) = value.first() {
+                capabilities |= match code {
+                    1 => Capabilities::MULTIPROTOCOL,
+                    2 => Capabilities::ROUTE_REFRESH,
+                    65 => Capabilities::FOUR_OCTET_ASN,
+                    64 => Capabilities::GRACEFUL_RESTART,
+                    _ => Capabilities::empty(),
+                };
+            }
+        }
+
+        offset += 2 + len;
+    }
+
+    capabilities
+}
+
+fn encode_update(update: &Update) -> Vec<u8> {
+    let mut body = Vec::new();
+
+    let withdrawn = encode_nlri_list(&update.withdrawn);
+    body.extend_from_slice(&(withdrawn.len() as u16).to_be_bytes());
+    body.extend_from_slice(&withdrawn);
+
+    // No path attributes beyond what callers attach out-of-band for now.
+    body.extend_from_slice(&0u16.to_be_bytes());
+
+    body.extend_from_slice(&encode_nlri_list(&update.announced));
+
+    body
+}
+
+fn decode_update(body: &[u8]) -> Update {
+    let mut offset = 0;
+    let withdrawn_len = read_u16(body, offset) as usize;
+    offset += 2;
+    let withdrawn = read_nlri_section(body, &mut offset, withdrawn_len);
+
+    let attributes_len = read_u16(body, offset) as usize;
+    offset += 2 + attributes_len;
+
+    // Announced NLRI carry no length prefix; they run to the end of the body.
+    let announced_len = body.len().saturating_sub(offset);
+    let announced = read_nlri_section(body, &mut offset, announced_len);
+
+    Update {
+        withdrawn,
+        announced,
+    }
+}
+
+fn encode_nlri_list(list: &[Nlri]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for nlri in list {
+        bytes.push(nlri.prefix_len);
+        bytes.extend_from_slice(&nlri.prefix);
+    }
+
+    bytes
+}
+
+fn read_nlri_section(body: &[u8], offset: &mut usize, len: usize) -> Vec<Nlri> {
+    let end = (*offset + len).min(body.len());
+    let mut nlris = Vec::new();
+
+    while *offset < end {
+        let Some(&prefix_len) = body.get(*offset) else {
+            break;
+        };
+        let octets = prefix_len.div_ceil(8) as usize;
+        let Some(prefix) = body.get(*offset + 1..*offset + 1 + octets) else {
+            break;
+        };
+
+        nlris.push(Nlri {
+            prefix_len,
+            prefix: prefix.to_vec(),
+        });
+        *offset += 1 + octets;
+    }
+
+    *offset = end;
+    nlris
+}
+
+fn read_u16(body: &[u8], offset: usize) -> u16 {
+    match body.get(offset..offset + 2) {
+        Some([high, low]) => u16::from_be_bytes([*high, *low]),
+        _ => 0,
+    }
+}
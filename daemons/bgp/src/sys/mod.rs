@@ -0,0 +1,3 @@
+pub mod capabilities;
+pub mod message;
+pub mod session;
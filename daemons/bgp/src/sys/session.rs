@@ -0,0 +1,182 @@
+use std::net::SocketAddr;
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::mpsc,
+};
+
+use crate::sys::{
+    capabilities::Capabilities,
+    message::{self, Message, Nlri, Open, Update},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Message(#[from] message::Error),
+    #[error("peer closed the connection")]
+    UnexpectedClose,
+    #[error("peer sent `{0:?}` while session was in state `{1:?}`")]
+    UnexpectedMessage(Box<Message>, SessionState),
+    #[error("peer's OPEN advertised ASN {actual}, but this neighbor was configured with ASN {expected}")]
+    UnexpectedPeerAsn { expected: u16, actual: u16 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    Idle,
+    Connect,
+    OpenSent,
+    OpenConfirm,
+    Established,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Announce(Nlri),
+    Withdraw(Nlri),
+}
+
+pub struct SessionConfig {
+    pub local_asn: u16,
+    pub local_router_id: u32,
+    pub hold_time: u16,
+    pub capabilities: Capabilities,
+    /// The ASN this neighbor was configured with, if known. When set, the
+    /// peer's OPEN message must advertise a matching ASN or the session is
+    /// rejected instead of being silently established under the wrong AS.
+    pub expected_peer_asn: Option<u16>,
+}
+
+/// Drives a single peer through the standard BGP FSM over one TCP
+/// connection: Idle -> Connect -> OpenSent -> OpenConfirm -> Established.
+pub struct Session {
+    config: SessionConfig,
+    state: SessionState,
+    peer_capabilities: Capabilities,
+    recv_buffer: Vec<u8>,
+}
+
+impl Session {
+    pub fn new(config: SessionConfig) -> Self {
+        Self {
+            config,
+            state: SessionState::Idle,
+            peer_capabilities: Capabilities::empty(),
+            recv_buffer: Vec::new(),
+        }
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// The capabilities both peers advertised, i.e. what this session may
+    /// actually use once it reaches [`SessionState::Established`].
+    pub fn negotiated_capabilities(&self) -> Capabilities {
+        self.config.capabilities.intersect(self.peer_capabilities)
+    }
+
+    pub async fn run(
+        mut self,
+        peer: SocketAddr,
+        events: mpsc::UnboundedSender<Event>,
+    ) -> Result<(), Error> {
+        self.state = SessionState::Connect;
+        let mut stream = TcpStream::connect(peer).await?;
+
+        self.send(&mut stream, Message::Open(self.local_open())).await?;
+        self.state = SessionState::OpenSent;
+
+        let message = self.recv(&mut stream).await?;
+        let Message::Open(peer_open) = message.clone() else {
+            return Err(Error::UnexpectedMessage(Box::new(message), self.state));
+        };
+        self.peer_capabilities = peer_open.capabilities;
+
+        if let Some(expected) = self.config.expected_peer_asn {
+            if peer_open.asn != expected {
+                return Err(Error::UnexpectedPeerAsn {
+                    expected,
+                    actual: peer_open.asn,
+                });
+            }
+        }
+
+        self.send(&mut stream, Message::Keepalive).await?;
+        self.state = SessionState::OpenConfirm;
+
+        match self.recv(&mut stream).await? {
+            Message::Keepalive => {}
+            other => return Err(Error::UnexpectedMessage(Box::new(other), self.state)),
+        }
+        self.state = SessionState::Established;
+
+        tracing::info!(
+            "BGP session with {peer} established, negotiated capabilities {:?}",
+            self.negotiated_capabilities()
+        );
+
+        loop {
+            match self.recv(&mut stream).await? {
+                Message::Update(update) => self.dispatch(update, &events),
+                Message::Keepalive => {}
+                Message::Notification { code, subcode } => {
+                    tracing::warn!("BGP peer {peer} sent NOTIFICATION {code}/{subcode}");
+                    return Ok(());
+                }
+                other => return Err(Error::UnexpectedMessage(Box::new(other), self.state)),
+            }
+        }
+    }
+
+    fn local_open(&self) -> Open {
+        Open {
+            asn: self.config.local_asn,
+            hold_time: self.config.hold_time,
+            router_id: self.config.local_router_id,
+            capabilities: self.config.capabilities,
+        }
+    }
+
+    fn dispatch(&self, update: Update, events: &mpsc::UnboundedSender<Event>) {
+        for nlri in update.announced {
+            events.send(Event::Announce(nlri)).ok();
+        }
+        for nlri in update.withdrawn {
+            events.send(Event::Withdraw(nlri)).ok();
+        }
+    }
+
+    async fn send(&self, stream: &mut TcpStream, message: Message) -> Result<(), Error> {
+        stream.write_all(&message.encode()).await?;
+        Ok(())
+    }
+
+    /// Reads one message off the wire. TCP doesn't preserve BGP message
+    /// boundaries, so a single `read()` routinely returns more than one
+    /// message back-to-back; whatever `Message::decode` doesn't consume is
+    /// kept in `recv_buffer` for the next call instead of being dropped.
+    async fn recv(&mut self, stream: &mut TcpStream) -> Result<Message, Error> {
+        loop {
+            match Message::decode(&self.recv_buffer) {
+                Ok((message, consumed)) => {
+                    self.recv_buffer.drain(..consumed);
+                    return Ok(message);
+                }
+                Err(message::Error::Truncated) => {}
+                Err(err) => return Err(err.into()),
+            }
+
+            let mut chunk = [0u8; 4096];
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(Error::UnexpectedClose);
+            }
+            self.recv_buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
@@ -2,9 +2,13 @@ use std::pin::Pin;
 
 use futures::Stream;
 
+mod heartbeat;
 mod netns;
+mod services;
 
+pub use heartbeat::{Heartbeat, HeartbeatStream};
 pub use netns::{NetnsService, NetnsServiceServer};
+pub use services::Services;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use futures::StreamExt;
 use netns::{Netns, NetnsWatcher, NetnsWatcherStream};
 use tonic::Response;
 
-use crate::api::{Error, ResponseStream};
+use crate::api::{Error, Heartbeat, HeartbeatStream, ResponseStream, Services};
 
 mod proto {
     tonic::include_proto!("kernel.netns");
@@ -12,22 +12,52 @@ mod proto {
 
 pub use proto::netns_service_server::NetnsServiceServer;
 
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+impl Heartbeat for proto::WatchNetnsEvent {
+    fn nop() -> Self {
+        Self {
+            kind: Some(proto::watch_netns_event::Kind::Nop(proto::Nop {})),
+        }
+    }
+}
+
+impl From<Services> for proto::Services {
+    fn from(services: Services) -> Self {
+        Self {
+            features: services.bits(),
+            protocol_version: super::services::PROTOCOL_VERSION,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct NetnsService {
     netns_watcher: Arc<NetnsWatcher>,
+    services: Services,
 }
 
 impl NetnsService {
-    pub fn new() -> Result<Self, Error> {
+    pub async fn new(services: Services) -> Result<Self, Error> {
         Ok(Self {
-            netns_watcher: NetnsWatcher::new()?,
+            netns_watcher: NetnsWatcher::new().await?,
+            services,
         })
     }
 }
 
 #[tonic::async_trait]
 impl proto::netns_service_server::NetnsService for NetnsService {
-    type WatchNetnsStream = ResponseStream<proto::NetnsList>;
+    type WatchNetnsStream = ResponseStream<proto::WatchNetnsEvent>;
+
+    async fn hello(
+        &self,
+        _request: tonic::Request<proto::HelloRequest>,
+    ) -> tonic::Result<tonic::Response<proto::HelloResponse>> {
+        Ok(Response::new(proto::HelloResponse {
+            services: Some(self.services.into()),
+        }))
+    }
 
     async fn watch_netns(
         &self,
@@ -35,17 +65,22 @@ impl proto::netns_service_server::NetnsService for NetnsService {
     ) -> tonic::Result<tonic::Response<Self::WatchNetnsStream>> {
         let netns_watcher_stream =
             NetnsWatcherStream::new(self.netns_watcher.clone()).map(|list| {
-                Ok(proto::NetnsList {
-                    list: list
-                        .into_iter()
-                        .map(|netns| match netns {
-                            Netns::Default => proto::Netns { name: None },
-                            Netns::Named(name) => proto::Netns { name: Some(name) },
-                        })
-                        .collect(),
+                Ok(proto::WatchNetnsEvent {
+                    kind: Some(proto::watch_netns_event::Kind::List(proto::NetnsList {
+                        list: list
+                            .into_iter()
+                            .map(|netns| match netns {
+                                Netns::Default => proto::Netns { name: None },
+                                Netns::Named(name) => proto::Netns { name: Some(name) },
+                            })
+                            .collect(),
+                    })),
                 })
             });
 
-        Ok(Response::new(Box::pin(netns_watcher_stream)))
+        Ok(Response::new(Box::pin(HeartbeatStream::new(
+            netns_watcher_stream,
+            HEARTBEAT_INTERVAL,
+        ))))
     }
 }
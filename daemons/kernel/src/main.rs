@@ -1,17 +1,30 @@
 mod api;
 mod sys;
 
-use std::{net::SocketAddr, str::FromStr};
+use std::{net::SocketAddr, path::PathBuf, str::FromStr};
 
+use api::Services;
+use sys::peering::{IdentityError, Keypair, PeerId, PeerMesh};
+use tokio::net::TcpListener;
 use tonic::transport;
 use tracing_subscriber::FmtSubscriber;
 
+const PEERING_ADDR: &str = "[::1]:50061";
+const KEYPAIR_PATH_VAR: &str = "NETWORKER_KERNEL_KEYPAIR_PATH";
+const PEERS_VAR: &str = "NETWORKER_KERNEL_PEERS";
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Api(#[from] api::Error),
     #[error(transparent)]
     Transport(#[from] transport::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+    #[error("invalid entry `{0}` in {PEERS_VAR}, expected `<peer id hex>@<ip>:<port>`")]
+    InvalidPeerEntry(String),
 }
 
 #[tokio::main]
@@ -25,10 +38,114 @@ async fn main() -> Result<(), Error> {
     )
     .expect("tracing setup failed");
 
+    let keypair = match std::env::var_os(KEYPAIR_PATH_VAR) {
+        Some(path) => Keypair::load_or_generate(&PathBuf::from(path))?,
+        None => {
+            tracing::warn!(
+                "{KEYPAIR_PATH_VAR} is unset; generating an ephemeral keypair, this node's \
+                 PeerId will change on every restart"
+            );
+            Keypair::generate()
+        }
+    };
+
+    let mesh = PeerMesh::new(keypair);
+
+    for (peer, address) in known_peers()? {
+        mesh.add_known_peer(peer, address);
+    }
+
+    spawn_peering_listener(mesh.clone()).await?;
+    spawn_peering_log(mesh.clone());
+
+    let services = Services::empty()
+        .with_netns_watch(true)
+        .with_heartbeat(true);
+
     transport::Server::builder()
-        .add_service(api::NetnsServiceServer::new(api::NetnsService::new()?))
+        .add_service(api::NetnsServiceServer::new(
+            api::NetnsService::new(services).await?,
+        ))
         .serve(SocketAddr::from_str("[::1]:50051").unwrap())
         .await?;
 
     Ok(())
 }
+
+/// Parses `NETWORKER_KERNEL_PEERS`, a comma-separated list of
+/// `<peer id hex>@<ip>:<port>` entries, into the known peers this node
+/// should keep an outbound connection to.
+fn known_peers() -> Result<Vec<(PeerId, SocketAddr)>, Error> {
+    let Some(entries) = std::env::var_os(PEERS_VAR) else {
+        return Ok(Vec::new());
+    };
+    let entries = entries.to_string_lossy();
+
+    entries
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (peer, address) = entry
+                .trim()
+                .split_once('@')
+                .ok_or_else(|| Error::InvalidPeerEntry(entry.to_string()))?;
+
+            let peer = PeerId::from_hex(peer).map_err(|_| Error::InvalidPeerEntry(entry.to_string()))?;
+            let address = address
+                .parse()
+                .map_err(|_| Error::InvalidPeerEntry(entry.to_string()))?;
+
+            Ok((peer, address))
+        })
+        .collect()
+}
+
+/// Logs mesh membership changes as they happen. Other subsystems that want
+/// to act on the peer set (e.g. gating BGP/RIB sync on mesh connectivity)
+/// can follow the same pattern: `mesh.subscribe()` for events, or
+/// `mesh.peers()` for a point-in-time snapshot.
+fn spawn_peering_log(mesh: std::sync::Arc<PeerMesh>) {
+    let mut events = mesh.subscribe();
+
+    tokio::spawn(async move {
+        while let Ok(event) = events.recv().await {
+            let peer_count = mesh.peers().len();
+
+            match event {
+                sys::peering::PeerEvent::Connected(peer) => {
+                    tracing::info!("peer `{peer}` connected ({peer_count} peers now known)");
+                }
+                sys::peering::PeerEvent::Disconnected(peer) => {
+                    tracing::info!("peer `{peer}` disconnected ({peer_count} peers now known)");
+                }
+                sys::peering::PeerEvent::Message(peer, payload) => {
+                    tracing::debug!("received {} bytes from peer `{peer}`", payload.len());
+                }
+            }
+        }
+    });
+}
+
+/// Accepts inbound peering connections and hands each authenticated one off
+/// to the mesh, which keeps it alive (and replaces it on reconnect).
+async fn spawn_peering_listener(mesh: std::sync::Arc<PeerMesh>) -> Result<(), Error> {
+    let listener = TcpListener::bind(PEERING_ADDR).await?;
+    tracing::info!("Peering listener is bound on `{PEERING_ADDR}`");
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _addr)) = listener.accept().await else {
+                continue;
+            };
+            let mesh = mesh.clone();
+
+            tokio::spawn(async move {
+                if let Err(err) = mesh.accept_inbound(stream).await {
+                    tracing::warn!("peering handshake failed: {err}");
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
@@ -1,24 +1,20 @@
 use std::{
     collections::HashSet,
     fmt::{self, Display, Formatter},
-    fs::{File, create_dir},
+    fs::File,
+    os::unix::fs::MetadataExt,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use futures::Stream;
-use nix::{
-    dir::Dir,
-    fcntl::OFlag,
-    libc::IN_ISDIR,
-    sched::{CloneFlags, setns, unshare},
-    sys::stat::{Mode, stat},
-};
+use nix::sched::{CloneFlags, setns, unshare};
 use notify::{
     Config, EventKind, INotifyWatcher, RecursiveMode, Watcher,
     event::{CreateKind, ModifyKind, RemoveKind, RenameMode},
 };
 use tokio::{
+    fs,
     sync::{Notify, RwLock},
     task,
 };
@@ -50,37 +46,25 @@ impl Netns {
         Self::Named(netns_name.as_ref().to_string())
     }
 
-    pub fn list() -> Vec<Netns> {
+    pub async fn list() -> Vec<Netns> {
         let mut netns = vec![Netns::Default];
-        let Ok(default_stat) = stat(DEAULT_NETNS_PATH) else {
+        let Ok(default_metadata) = fs::metadata(DEAULT_NETNS_PATH).await else {
             return netns;
         };
-        let Ok(mut netns_dir) = Dir::open(
-            NETNS_PATH,
-            OFlag::O_RDONLY | OFlag::O_CLOEXEC | OFlag::O_DIRECTORY,
-            Mode::empty(),
-        ) else {
+        let Ok(mut netns_dir) = fs::read_dir(NETNS_PATH).await else {
             return netns;
         };
 
-        for entry in netns_dir.iter() {
-            let Ok(entry) = entry else {
-                continue;
+        loop {
+            let Ok(Some(entry)) = netns_dir.next_entry().await else {
+                break;
             };
-            let file_name = entry.file_name().to_string_lossy().to_string();
-
-            if [".", ".."].contains(&file_name.as_str()) {
-                continue;
-            }
-
-            let file_path = Path::new(NETNS_PATH).join(&file_name);
-            let Ok(netns_stat) = stat(&file_path) else {
+            let file_path = entry.path();
+            let Ok(netns_metadata) = fs::metadata(&file_path).await else {
                 continue;
             };
 
-            if (netns_stat.st_mode & IN_ISDIR == IN_ISDIR)
-                || (netns_stat.st_ino == default_stat.st_ino)
-            {
+            if netns_metadata.is_dir() || netns_metadata.ino() == default_metadata.ino() {
                 continue;
             }
 
@@ -139,11 +123,11 @@ pub struct NetnsWatcher {
 }
 
 impl NetnsWatcher {
-    pub fn new() -> Result<Arc<Self>, Error> {
-        let default_stat = stat(DEAULT_NETNS_PATH)?;
+    pub async fn new() -> Result<Arc<Self>, Error> {
+        let default_metadata = fs::metadata(DEAULT_NETNS_PATH).await?;
         let netns_watcher = Arc::new(Self {
-            default_ino: default_stat.st_ino,
-            list: RwLock::new((HashSet::from_iter(Netns::list()), 0)),
+            default_ino: default_metadata.ino(),
+            list: RwLock::new((HashSet::from_iter(Netns::list().await), 0)),
             notif: Notify::new(),
         });
 
@@ -168,8 +152,8 @@ impl NetnsWatcher {
         )?;
         let netns_path = Path::new(NETNS_PATH);
 
-        if !netns_path.is_dir() {
-            create_dir(netns_path)?;
+        if !fs::try_exists(netns_path).await? {
+            fs::create_dir(netns_path).await?;
         }
 
         file_watcher.watch(netns_path, RecursiveMode::NonRecursive)?;
@@ -215,25 +199,32 @@ impl NetnsWatcher {
     }
 
     async fn insert<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
-        let mut list = self.list.write().await;
-        let mut changed = false;
+        // Resolve every path's metadata off the lock so the inotify loop never
+        // blocks the reactor (or other readers) on the filesystem.
+        let mut resolved = Vec::new();
 
         for path in paths {
-            let Ok(netns_stat) = stat(path) else {
+            let Ok(netns_metadata) = fs::metadata(path).await else {
                 continue;
             };
 
-            if (netns_stat.st_mode & IN_ISDIR == IN_ISDIR)
-                || (netns_stat.st_ino == self.default_ino)
-            {
+            if netns_metadata.is_dir() || netns_metadata.ino() == self.default_ino {
                 continue;
             }
 
-            if list
-                .0
-                .insert(Netns::Named(path.to_string_lossy().to_string()))
-            {
-                tracing::info!("Netns added `{}`", path.display());
+            resolved.push(Netns::Named(path.to_string_lossy().to_string()));
+        }
+
+        if resolved.is_empty() {
+            return;
+        }
+
+        let mut list = self.list.write().await;
+        let mut changed = false;
+
+        for netns in resolved {
+            if list.0.insert(netns.clone()) {
+                tracing::info!("Netns added `{netns}`");
                 changed = true;
             }
         }
@@ -244,15 +235,21 @@ impl NetnsWatcher {
     }
 
     async fn remove<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
+        let to_remove: Vec<Netns> = paths
+            .into_iter()
+            .map(|path| Netns::Named(path.to_string_lossy().to_string()))
+            .collect();
+
+        if to_remove.is_empty() {
+            return;
+        }
+
         let mut list = self.list.write().await;
         let mut changed = false;
 
-        for path in paths {
-            if list
-                .0
-                .remove(&Netns::Named(path.to_string_lossy().to_string()))
-            {
-                tracing::info!("Netns removed `{}`", path.display());
+        for netns in to_remove {
+            if list.0.remove(&netns) {
+                tracing::info!("Netns removed `{netns}`");
                 changed = true;
             }
         }
@@ -0,0 +1,93 @@
+use std::net::SocketAddr;
+
+use kuska_handshake::{BoxStreamRead, BoxStreamWrite, HandshakeComplete, sync_handshake_client, sync_handshake_server};
+use tokio::net::TcpStream;
+
+use super::identity::{Keypair, PeerId};
+
+/// Identifies the app so unrelated networker clusters can't handshake with
+/// each other even if they happen to reach the same socket.
+const NETWORK_IDENTIFIER: [u8; 32] = *b"networker-peering-mesh-v1-------";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Handshake(#[from] kuska_handshake::Error),
+    #[error("peer authenticated with an unexpected public key")]
+    UnexpectedPeer,
+}
+
+/// One live, authenticated, encrypted connection to a peer. Reads and
+/// writes go through the box-stream established by the handshake, so every
+/// frame on the wire is already sealed with the session key.
+pub struct PeerConnection {
+    pub peer: PeerId,
+    pub address: SocketAddr,
+    reader: BoxStreamRead<tokio::net::tcp::OwnedReadHalf>,
+    writer: BoxStreamWrite<tokio::net::tcp::OwnedWriteHalf>,
+}
+
+impl PeerConnection {
+    pub async fn send(&mut self, payload: &[u8]) -> Result<(), Error> {
+        self.writer.write(payload).await?;
+        Ok(())
+    }
+
+    pub async fn recv(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.reader.read().await?)
+    }
+}
+
+/// Connects out to `address` and performs the client side of the mutual
+/// handshake, authenticating the remote node against `expected_peer` when
+/// the caller already knows who it's dialing.
+pub async fn connect(
+    keypair: &Keypair,
+    address: SocketAddr,
+    expected_peer: Option<PeerId>,
+) -> Result<PeerConnection, Error> {
+    let stream = TcpStream::connect(address).await?;
+    let (read_half, write_half) = stream.into_split();
+
+    let HandshakeComplete {
+        peer_public_key,
+        box_reader,
+        box_writer,
+        ..
+    } = sync_handshake_client(read_half, write_half, &NETWORK_IDENTIFIER, &keypair.signing_key).await?;
+
+    let peer = PeerId::from_verifying_key(&peer_public_key);
+    if expected_peer.is_some_and(|expected| expected != peer) {
+        return Err(Error::UnexpectedPeer);
+    }
+
+    Ok(PeerConnection {
+        peer,
+        address,
+        reader: box_reader,
+        writer: box_writer,
+    })
+}
+
+/// Accepts an inbound connection and performs the server side of the
+/// handshake, authenticating whichever peer dials in by its public key.
+pub async fn accept(keypair: &Keypair, stream: TcpStream) -> Result<PeerConnection, Error> {
+    let address = stream.peer_addr()?;
+    let (read_half, write_half) = stream.into_split();
+
+    let HandshakeComplete {
+        peer_public_key,
+        box_reader,
+        box_writer,
+        ..
+    } = sync_handshake_server(read_half, write_half, &NETWORK_IDENTIFIER, &keypair.signing_key).await?;
+
+    Ok(PeerConnection {
+        peer: PeerId::from_verifying_key(&peer_public_key),
+        address,
+        reader: box_reader,
+        writer: box_writer,
+    })
+}
@@ -0,0 +1,101 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::Path,
+};
+
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::rngs::OsRng;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("keypair file must hold a {0}-byte seed, found {1} bytes")]
+    InvalidLength(usize, usize),
+    #[error("`{0}` is not a 64-character hex-encoded peer id")]
+    InvalidPeerId(String),
+}
+
+/// A peer's long-lived identity: the public half of its handshake keypair,
+/// also used to address it across reconnects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PeerId([u8; 32]);
+
+impl PeerId {
+    pub fn from_verifying_key(key: &VerifyingKey) -> Self {
+        Self(key.to_bytes())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Parses the hex encoding produced by [`Display`], as used in known-peer
+    /// configuration.
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        if hex.len() != 64 {
+            return Err(Error::InvalidPeerId(hex.to_string()));
+        }
+
+        let mut bytes = [0u8; 32];
+        for (index, chunk) in hex.as_bytes().chunks(2).enumerate() {
+            let pair = std::str::from_utf8(chunk).map_err(|_| Error::InvalidPeerId(hex.to_string()))?;
+            bytes[index] =
+                u8::from_str_radix(pair, 16).map_err(|_| Error::InvalidPeerId(hex.to_string()))?;
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+impl Display for PeerId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The long-lived keypair a networker node uses to authenticate itself to
+/// peers during the handshake. Kept stable across restarts (via
+/// [`Keypair::load_or_generate`]) so a peer's [`PeerId`] doesn't change
+/// every time the process restarts.
+pub struct Keypair {
+    pub(super) signing_key: SigningKey,
+    pub id: PeerId,
+}
+
+impl Keypair {
+    pub fn generate() -> Self {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let id = PeerId::from_verifying_key(&signing_key.verifying_key());
+
+        Self { signing_key, id }
+    }
+
+    /// Loads the signing key seed from `path`, or generates a fresh keypair
+    /// and writes its seed to `path` if the file doesn't exist yet. This is
+    /// what lets a node's [`PeerId`] survive a restart instead of changing
+    /// on every launch.
+    pub fn load_or_generate(path: &Path) -> Result<Self, Error> {
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                let seed: [u8; 32] = bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| Error::InvalidLength(32, bytes.len()))?;
+                let signing_key = SigningKey::from_bytes(&seed);
+                let id = PeerId::from_verifying_key(&signing_key.verifying_key());
+
+                Ok(Self { signing_key, id })
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let keypair = Self::generate();
+                std::fs::write(path, keypair.signing_key.to_bytes())?;
+                Ok(keypair)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
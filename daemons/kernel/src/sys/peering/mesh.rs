@@ -0,0 +1,220 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use arc_swap::ArcSwap;
+use tokio::{
+    sync::{broadcast, mpsc},
+    task::JoinHandle,
+    time::sleep,
+};
+
+use super::{
+    connection::{self, PeerConnection},
+    identity::{Keypair, PeerId},
+};
+
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub enum PeerEvent {
+    Connected(PeerId),
+    Disconnected(PeerId),
+    Message(PeerId, Vec<u8>),
+}
+
+/// A live, outbound-capable handle to one peer. Subsystems that want to
+/// talk to a peer go through this; the raw [`PeerConnection`] stays owned
+/// by its connection's background task.
+pub struct PeerHandle {
+    pub peer: PeerId,
+    pub address: SocketAddr,
+    generation: u64,
+    outbound: mpsc::UnboundedSender<Vec<u8>>,
+}
+
+impl PeerHandle {
+    pub fn send(&self, payload: Vec<u8>) {
+        self.outbound.send(payload).ok();
+    }
+}
+
+/// Maintains one live connection per known peer in a full mesh, reconnecting
+/// with backoff whenever a link drops. Other subsystems read the current
+/// peer set straight off the `ArcSwap` instead of locking, and only the
+/// connection that wins the race for a given peer is ever kept — a
+/// generation counter lets a late-arriving socket recognize it lost and
+/// tear itself down instead of racing the winner.
+pub struct PeerMesh {
+    keypair: Arc<Keypair>,
+    peers: ArcSwap<HashMap<PeerId, Arc<PeerHandle>>>,
+    generation: AtomicU64,
+    events: broadcast::Sender<PeerEvent>,
+}
+
+impl PeerMesh {
+    pub fn new(keypair: Keypair) -> Arc<Self> {
+        let (events, _receiver) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+        Arc::new(Self {
+            keypair: Arc::new(keypair),
+            peers: ArcSwap::from_pointee(HashMap::new()),
+            generation: AtomicU64::new(0),
+            events,
+        })
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<PeerEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn peers(&self) -> Arc<HashMap<PeerId, Arc<PeerHandle>>> {
+        self.peers.load_full()
+    }
+
+    /// Registers a known peer address and keeps a connection to it alive
+    /// for as long as the mesh itself is alive, reconnecting with
+    /// exponential backoff whenever the link drops.
+    pub fn add_known_peer(self: &Arc<Self>, expected_peer: PeerId, address: SocketAddr) {
+        let mesh = self.clone();
+
+        tokio::spawn(async move {
+            let mut backoff = INITIAL_BACKOFF;
+
+            loop {
+                match connection::connect(&mesh.keypair, address, Some(expected_peer)).await {
+                    Ok(connection) => {
+                        backoff = INITIAL_BACKOFF;
+                        let mut events = mesh.subscribe();
+                        mesh.clone().adopt(connection);
+
+                        // Only dial again once this peer's current link drops.
+                        loop {
+                            match events.recv().await {
+                                Ok(PeerEvent::Disconnected(peer)) if peer == expected_peer => break,
+                                Err(broadcast::error::RecvError::Closed) => break,
+                                _ => {}
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        tracing::warn!("failed to connect to peer at {address}: {err}");
+                    }
+                }
+
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        });
+    }
+
+    /// Adopts an already-authenticated inbound connection, e.g. from the
+    /// mesh's listener loop.
+    pub fn handle_inbound(self: &Arc<Self>, connection: PeerConnection) {
+        self.clone().adopt(connection);
+    }
+
+    /// Performs the server side of the handshake against a freshly accepted
+    /// TCP stream and, on success, adopts the resulting connection.
+    pub async fn accept_inbound(
+        self: &Arc<Self>,
+        stream: tokio::net::TcpStream,
+    ) -> Result<(), connection::Error> {
+        let connection = connection::accept(&self.keypair, stream).await?;
+        self.handle_inbound(connection);
+        Ok(())
+    }
+
+    fn adopt(self: Arc<Self>, connection: PeerConnection) {
+        let peer = connection.peer;
+        let address = connection.address;
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let (outbound_tx, outbound_rx) = mpsc::unbounded_channel();
+
+        let handle = Arc::new(PeerHandle {
+            peer,
+            address,
+            generation,
+            outbound: outbound_tx,
+        });
+
+        let mut adopted = false;
+        self.peers.rcu(|current| {
+            let mut next = HashMap::clone(current);
+
+            match next.get(&peer) {
+                Some(existing) if existing.generation > generation => adopted = false,
+                _ => {
+                    next.insert(peer, handle.clone());
+                    adopted = true;
+                }
+            }
+
+            next
+        });
+
+        if !adopted {
+            // Lost the race against a fresher connection for this peer.
+            return;
+        }
+
+        self.events.send(PeerEvent::Connected(peer)).ok();
+        tokio::spawn(self.clone().drive_io(connection, outbound_rx, generation));
+    }
+
+    async fn drive_io(
+        self: Arc<Self>,
+        mut connection: PeerConnection,
+        mut outbound_rx: mpsc::UnboundedReceiver<Vec<u8>>,
+        generation: u64,
+    ) {
+        let peer = connection.peer;
+
+        loop {
+            tokio::select! {
+                outgoing = outbound_rx.recv() => {
+                    let Some(payload) = outgoing else { break };
+                    if connection.send(&payload).await.is_err() {
+                        break;
+                    }
+                }
+                incoming = connection.recv() => {
+                    match incoming {
+                        Ok(Some(payload)) => {
+                            self.events.send(PeerEvent::Message(peer, payload)).ok();
+                        }
+                        _ => break,
+                    }
+                }
+            }
+        }
+
+        self.retire(peer, generation);
+    }
+
+    fn retire(&self, peer: PeerId, generation: u64) {
+        let mut retired = false;
+        self.peers.rcu(|current| {
+            let mut next = HashMap::clone(current);
+
+            retired = next.get(&peer).is_some_and(|existing| existing.generation == generation);
+            if retired {
+                next.remove(&peer);
+            }
+
+            next
+        });
+
+        if retired {
+            self.events.send(PeerEvent::Disconnected(peer)).ok();
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod connection;
+pub mod identity;
+pub mod mesh;
+
+pub use connection::PeerConnection;
+pub use identity::{Error as IdentityError, Keypair, PeerId};
+pub use mesh::{PeerEvent, PeerHandle, PeerMesh};
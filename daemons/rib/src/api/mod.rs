@@ -0,0 +1,27 @@
+use std::pin::Pin;
+
+use futures::Stream;
+
+mod heartbeat;
+mod rib;
+mod services;
+
+pub use heartbeat::{Heartbeat, HeartbeatStream};
+pub use rib::{RibService, RibServiceServer};
+pub use services::Services;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Netns(#[from] ::netns::Error),
+    #[error(transparent)]
+    Rib(#[from] crate::sys::rib::Error),
+}
+
+impl From<Error> for tonic::Status {
+    fn from(err: Error) -> Self {
+        tonic::Status::internal(err.to_string())
+    }
+}
+
+type ResponseStream<T> = Pin<Box<dyn Stream<Item = tonic::Result<T>> + Send>>;
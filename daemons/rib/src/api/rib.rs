@@ -0,0 +1,253 @@
+use std::{net::IpAddr, time::Duration};
+
+use futures::StreamExt;
+use netns::Netns;
+use tonic::Response;
+
+use crate::{
+    api::{Error, Heartbeat, HeartbeatStream, ResponseStream, Services},
+    sys::rib::{Address, Link, RibRuntimes, Route, RouteEvent},
+};
+
+mod proto {
+    tonic::include_proto!("rib");
+}
+
+pub use proto::rib_service_server::RibServiceServer;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+impl Heartbeat for proto::RouteEvent {
+    fn nop() -> Self {
+        Self {
+            kind: Some(proto::route_event::Kind::Nop(proto::Nop {})),
+        }
+    }
+}
+
+impl From<Services> for proto::Services {
+    fn from(services: Services) -> Self {
+        Self {
+            features: services.bits(),
+            protocol_version: super::services::PROTOCOL_VERSION,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RibService {
+    runtimes: std::sync::Arc<RibRuntimes>,
+    services: Services,
+}
+
+impl RibService {
+    pub fn new(services: Services) -> Self {
+        if !services.supports(Services::RIB_WRITE) {
+            tracing::warn!("RibService started without advertising RIB_WRITE");
+        }
+
+        Self {
+            runtimes: RibRuntimes::new(),
+            services,
+        }
+    }
+
+    fn require_rib_write(&self) -> tonic::Result<()> {
+        if self.services.supports(Services::RIB_WRITE) {
+            Ok(())
+        } else {
+            Err(tonic::Status::unimplemented(
+                "this node did not advertise RIB_WRITE",
+            ))
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::rib_service_server::RibService for RibService {
+    type WatchRoutesStream = ResponseStream<proto::RouteEvent>;
+
+    async fn hello(
+        &self,
+        _request: tonic::Request<proto::HelloRequest>,
+    ) -> tonic::Result<Response<proto::HelloResponse>> {
+        Ok(Response::new(proto::HelloResponse {
+            services: Some(self.services.into()),
+        }))
+    }
+
+    async fn list_routes(
+        &self,
+        request: tonic::Request<proto::NetnsScopedRequest>,
+    ) -> tonic::Result<Response<proto::RouteList>> {
+        let netns = to_netns(request.into_inner().netns);
+        let routes = self
+            .runtimes
+            .list_routes(&netns)
+            .await
+            .map_err(Error::from)?;
+
+        Ok(Response::new(proto::RouteList {
+            routes: routes.into_iter().map(from_route).collect(),
+        }))
+    }
+
+    async fn watch_routes(
+        &self,
+        request: tonic::Request<proto::NetnsScopedRequest>,
+    ) -> tonic::Result<Response<Self::WatchRoutesStream>> {
+        let netns = to_netns(request.into_inner().netns);
+        let events = self
+            .runtimes
+            .watch_routes(&netns)
+            .await
+            .map_err(Error::from)?
+            .map(|event| Ok(from_route_event(event)));
+
+        Ok(Response::new(Box::pin(HeartbeatStream::new(
+            events,
+            HEARTBEAT_INTERVAL,
+        ))))
+    }
+
+    async fn add_route(
+        &self,
+        request: tonic::Request<proto::AddRouteRequest>,
+    ) -> tonic::Result<Response<()>> {
+        self.require_rib_write()?;
+
+        let request = request.into_inner();
+        let netns = to_netns(request.netns);
+        let route = to_route(
+            request
+                .route
+                .ok_or_else(|| tonic::Status::invalid_argument("missing route"))?,
+        )?;
+
+        self.runtimes
+            .add_route(&netns, route)
+            .await
+            .map_err(Error::from)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn delete_route(
+        &self,
+        request: tonic::Request<proto::DeleteRouteRequest>,
+    ) -> tonic::Result<Response<()>> {
+        self.require_rib_write()?;
+
+        let request = request.into_inner();
+        let netns = to_netns(request.netns);
+        let route = to_route(
+            request
+                .route
+                .ok_or_else(|| tonic::Status::invalid_argument("missing route"))?,
+        )?;
+
+        self.runtimes
+            .delete_route(&netns, route)
+            .await
+            .map_err(Error::from)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn list_addresses(
+        &self,
+        request: tonic::Request<proto::NetnsScopedRequest>,
+    ) -> tonic::Result<Response<proto::AddressList>> {
+        let netns = to_netns(request.into_inner().netns);
+        let addresses = self
+            .runtimes
+            .list_addresses(&netns)
+            .await
+            .map_err(Error::from)?;
+
+        Ok(Response::new(proto::AddressList {
+            addresses: addresses.into_iter().map(from_address).collect(),
+        }))
+    }
+
+    async fn list_links(
+        &self,
+        request: tonic::Request<proto::NetnsScopedRequest>,
+    ) -> tonic::Result<Response<proto::LinkList>> {
+        let netns = to_netns(request.into_inner().netns);
+        let links = self.runtimes.list_links(&netns).await.map_err(Error::from)?;
+
+        Ok(Response::new(proto::LinkList {
+            links: links.into_iter().map(from_link).collect(),
+        }))
+    }
+}
+
+fn to_netns(netns: Option<proto::Netns>) -> Netns {
+    match netns.and_then(|netns| netns.name) {
+        Some(name) => Netns::named(name),
+        None => Netns::Default,
+    }
+}
+
+fn to_route(route: proto::Route) -> tonic::Result<Route> {
+    Ok(Route {
+        destination: bytes_to_ip(&route.destination)?,
+        prefix_len: route.prefix_len as u8,
+        gateway: (!route.gateway.is_empty())
+            .then(|| bytes_to_ip(&route.gateway))
+            .transpose()?,
+        oif_index: route.oif_index,
+    })
+}
+
+fn from_route(route: Route) -> proto::Route {
+    proto::Route {
+        destination: ip_to_bytes(route.destination),
+        prefix_len: route.prefix_len as u32,
+        gateway: route.gateway.map(ip_to_bytes).unwrap_or_default(),
+        oif_index: route.oif_index,
+    }
+}
+
+fn from_route_event(event: RouteEvent) -> proto::RouteEvent {
+    let kind = match event {
+        RouteEvent::Added(route) => proto::route_event::Kind::Added(from_route(route)),
+        RouteEvent::Removed(route) => proto::route_event::Kind::Removed(from_route(route)),
+    };
+
+    proto::RouteEvent { kind: Some(kind) }
+}
+
+fn from_address(address: Address) -> proto::Address {
+    proto::Address {
+        address: ip_to_bytes(address.address),
+        prefix_len: address.prefix_len as u32,
+        link_index: address.link_index,
+    }
+}
+
+fn from_link(link: Link) -> proto::Link {
+    proto::Link {
+        index: link.index,
+        name: link.name,
+        up: link.up,
+    }
+}
+
+fn bytes_to_ip(bytes: &[u8]) -> tonic::Result<IpAddr> {
+    match bytes.len() {
+        4 => Ok(IpAddr::from(<[u8; 4]>::try_from(bytes).unwrap())),
+        16 => Ok(IpAddr::from(<[u8; 16]>::try_from(bytes).unwrap())),
+        _ => Err(tonic::Status::invalid_argument(
+            "address must be 4 or 16 bytes",
+        )),
+    }
+}
+
+fn ip_to_bytes(address: IpAddr) -> Vec<u8> {
+    match address {
+        IpAddr::V4(address) => address.octets().to_vec(),
+        IpAddr::V6(address) => address.octets().to_vec(),
+    }
+}
@@ -0,0 +1,35 @@
+mod api;
+mod sys;
+
+use std::{net::SocketAddr, str::FromStr};
+
+use api::Services;
+use tonic::transport;
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Api(#[from] api::Error),
+    #[error(transparent)]
+    Transport(#[from] transport::Error),
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing::subscriber::set_global_default(
+        FmtSubscriber::builder()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_env("NETWORKER_RIB_LOG"))
+            .finish(),
+    )
+    .expect("tracing setup failed");
+
+    let services = Services::empty().with_rib(true).with_heartbeat(true);
+
+    transport::Server::builder()
+        .add_service(api::RibServiceServer::new(api::RibService::new(services)))
+        .serve(SocketAddr::from_str("[::1]:50052").unwrap())
+        .await?;
+
+    Ok(())
+}
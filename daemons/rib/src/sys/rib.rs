@@ -0,0 +1,445 @@
+use std::{collections::HashMap, net::IpAddr, sync::Arc, thread};
+
+use netlink_packet_core::{NetlinkMessage, NetlinkPayload};
+use netlink_packet_route::{
+    RouteNetlinkMessage,
+    address::AddressAttribute,
+    link::{LinkAttribute, LinkFlags},
+    route::{RouteAttribute, RouteMessage},
+};
+use netlink_sys::{SocketAddr, TokioSocket, protocols::NETLINK_ROUTE};
+use netns::Netns;
+use rtnetlink::Handle;
+use tokio::sync::{RwLock, mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+const RTMGRP_IPV4_ROUTE: u32 = 0x40;
+const RTMGRP_IPV6_ROUTE: u32 = 0x400;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Netns(#[from] netns::Error),
+    #[error(transparent)]
+    Rtnetlink(#[from] rtnetlink::Error),
+    #[error("rib runtime for netns `{0}` is gone")]
+    RuntimeGone(Netns),
+}
+
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub destination: IpAddr,
+    pub prefix_len: u8,
+    pub gateway: Option<IpAddr>,
+    pub oif_index: u32,
+}
+
+#[derive(Debug, Clone)]
+pub enum RouteEvent {
+    Added(Route),
+    Removed(Route),
+}
+
+#[derive(Debug, Clone)]
+pub struct Address {
+    pub address: IpAddr,
+    pub prefix_len: u8,
+    pub link_index: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub index: u32,
+    pub name: String,
+    pub up: bool,
+}
+
+enum Command {
+    List(oneshot::Sender<Result<Vec<Route>, Error>>),
+    Add(Route, oneshot::Sender<Result<(), Error>>),
+    Delete(Route, oneshot::Sender<Result<(), Error>>),
+    Watch(mpsc::UnboundedSender<RouteEvent>),
+    ListAddresses(oneshot::Sender<Result<Vec<Address>, Error>>),
+    ListLinks(oneshot::Sender<Result<Vec<Link>, Error>>),
+}
+
+/// A dedicated OS thread running its own single-threaded Tokio runtime for a
+/// single namespace. `Netns::enter` permanently mutates whichever thread
+/// calls it, so every namespace gets its own thread to mutate instead of a
+/// pooled Tokio worker that other namespaces would otherwise inherit.
+struct NetnsRuntime {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl NetnsRuntime {
+    async fn spawn(netns: Netns) -> Result<Self, Error> {
+        let (commands_tx, commands_rx) = mpsc::unbounded_channel();
+        let (ready_tx, ready_rx) = oneshot::channel::<Result<(), Error>>();
+        let thread_netns = netns.clone();
+
+        thread::Builder::new()
+            .name(format!("rib-netns-{netns}"))
+            .spawn(move || Self::run(thread_netns, commands_rx, ready_tx))?;
+
+        ready_rx.await.map_err(|_| Error::RuntimeGone(netns))??;
+
+        Ok(Self {
+            commands: commands_tx,
+        })
+    }
+
+    fn run(
+        netns: Netns,
+        commands: mpsc::UnboundedReceiver<Command>,
+        ready: oneshot::Sender<Result<(), Error>>,
+    ) {
+        let initial_netns = match netns.enter() {
+            Ok(handle) => handle,
+            Err(err) => {
+                ready.send(Err(err.into())).ok();
+                return;
+            }
+        };
+
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                ready.send(Err(err.into())).ok();
+                return;
+            }
+        };
+
+        runtime.block_on(Self::serve(commands, ready));
+
+        if let Err(err) = initial_netns.close() {
+            tracing::error!("failed to restore initial netns after `{netns}`: {err}");
+        }
+    }
+
+    async fn serve(
+        mut commands: mpsc::UnboundedReceiver<Command>,
+        ready: oneshot::Sender<Result<(), Error>>,
+    ) {
+        let (connection, handle, _messages) = match rtnetlink::new_connection() {
+            Ok(parts) => parts,
+            Err(err) => {
+                ready.send(Err(err.into())).ok();
+                return;
+            }
+        };
+        tokio::spawn(connection);
+        ready.send(Ok(())).ok();
+
+        while let Some(command) = commands.recv().await {
+            match command {
+                Command::List(reply) => {
+                    reply.send(list_routes(&handle).await).ok();
+                }
+                Command::Add(route, reply) => {
+                    reply.send(add_route(&handle, route).await).ok();
+                }
+                Command::Delete(route, reply) => {
+                    reply.send(delete_route(&handle, route).await).ok();
+                }
+                Command::Watch(events) => {
+                    if let Err(err) = watch_routes(events) {
+                        tracing::error!("failed to start route watch: {err}");
+                    }
+                }
+                Command::ListAddresses(reply) => {
+                    reply.send(list_addresses(&handle).await).ok();
+                }
+                Command::ListLinks(reply) => {
+                    reply.send(list_links(&handle).await).ok();
+                }
+            }
+        }
+    }
+}
+
+async fn list_routes(handle: &Handle) -> Result<Vec<Route>, Error> {
+    use futures::TryStreamExt;
+
+    let mut routes = Vec::new();
+
+    for ip_version in [rtnetlink::IpVersion::V4, rtnetlink::IpVersion::V6] {
+        let mut stream = handle.route().get(ip_version).execute();
+
+        while let Some(message) = stream.try_next().await? {
+            if let Some(route) = route_from_message(&message) {
+                routes.push(route);
+            }
+        }
+    }
+
+    Ok(routes)
+}
+
+async fn add_route(handle: &Handle, route: Route) -> Result<(), Error> {
+    let request = handle.route().add().destination_prefix(route.destination, route.prefix_len);
+    let request = match route.gateway {
+        Some(gateway) => request.gateway(gateway),
+        None => request,
+    };
+
+    request.execute().await?;
+
+    Ok(())
+}
+
+async fn delete_route(handle: &Handle, route: Route) -> Result<(), Error> {
+    use futures::TryStreamExt;
+
+    let ip_version = match route.destination {
+        IpAddr::V4(_) => rtnetlink::IpVersion::V4,
+        IpAddr::V6(_) => rtnetlink::IpVersion::V6,
+    };
+    let mut stream = handle.route().get(ip_version).execute();
+
+    while let Some(message) = stream.try_next().await? {
+        if route_from_message(&message).is_some_and(|found| {
+            found.destination == route.destination && found.prefix_len == route.prefix_len
+        }) {
+            handle.route().del(message).execute().await?;
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn route_from_message(message: &RouteMessage) -> Option<Route> {
+    let mut destination = None;
+    let mut gateway = None;
+    let mut oif_index = 0;
+
+    for attribute in &message.attributes {
+        match attribute {
+            RouteAttribute::Destination(address) => {
+                destination = address.clone().try_into().ok();
+            }
+            RouteAttribute::Gateway(address) => {
+                gateway = address.clone().try_into().ok();
+            }
+            RouteAttribute::Oif(index) => oif_index = *index,
+            _ => {}
+        }
+    }
+
+    Some(Route {
+        destination: destination?,
+        prefix_len: message.header.destination_prefix_length,
+        gateway,
+        oif_index,
+    })
+}
+
+async fn list_addresses(handle: &Handle) -> Result<Vec<Address>, Error> {
+    use futures::TryStreamExt;
+
+    let mut addresses = Vec::new();
+    let mut stream = handle.address().get().execute();
+
+    while let Some(message) = stream.try_next().await? {
+        let mut address = None;
+
+        for attribute in &message.attributes {
+            if let AddressAttribute::Address(found) = attribute {
+                address = Some(*found);
+            }
+        }
+
+        if let Some(address) = address {
+            addresses.push(Address {
+                address,
+                prefix_len: message.header.prefix_len,
+                link_index: message.header.index,
+            });
+        }
+    }
+
+    Ok(addresses)
+}
+
+async fn list_links(handle: &Handle) -> Result<Vec<Link>, Error> {
+    use futures::TryStreamExt;
+
+    let mut links = Vec::new();
+    let mut stream = handle.link().get().execute();
+
+    while let Some(message) = stream.try_next().await? {
+        let mut name = None;
+
+        for attribute in &message.attributes {
+            if let LinkAttribute::IfName(found) = attribute {
+                name = Some(found.clone());
+            }
+        }
+
+        links.push(Link {
+            index: message.header.index,
+            name: name.unwrap_or_default(),
+            up: message.header.flags.contains(LinkFlags::Up),
+        });
+    }
+
+    Ok(links)
+}
+
+/// Drives a raw `RTMGRP_IPV4_ROUTE`/`RTMGRP_IPV6_ROUTE` multicast socket so
+/// route changes made outside of this process (or by the kernel itself)
+/// still reach `watch_routes` subscribers.
+fn watch_routes(events: mpsc::UnboundedSender<RouteEvent>) -> Result<(), Error> {
+    let mut socket = TokioSocket::new(NETLINK_ROUTE)?;
+    socket.socket_mut().bind(&SocketAddr::new(
+        0,
+        RTMGRP_IPV4_ROUTE | RTMGRP_IPV6_ROUTE,
+    ))?;
+
+    tokio::spawn(async move {
+        let mut buffer = vec![0u8; 64 * 1024];
+
+        loop {
+            let Ok(size) = socket.recv(&mut buffer).await else {
+                break;
+            };
+            let mut offset = 0;
+
+            while offset < size {
+                let Ok(message) =
+                    NetlinkMessage::<RouteNetlinkMessage>::deserialize(&buffer[offset..size])
+                else {
+                    break;
+                };
+                offset += message.header.length as usize;
+
+                let event = match message.payload {
+                    NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewRoute(message)) => {
+                        route_from_message(&message).map(RouteEvent::Added)
+                    }
+                    NetlinkPayload::InnerMessage(RouteNetlinkMessage::DelRoute(message)) => {
+                        route_from_message(&message).map(RouteEvent::Removed)
+                    }
+                    _ => None,
+                };
+
+                if let Some(event) = event {
+                    if events.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+pub struct RibRuntimes {
+    runtimes: RwLock<HashMap<Netns, Arc<NetnsRuntime>>>,
+}
+
+impl RibRuntimes {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            runtimes: RwLock::new(HashMap::new()),
+        })
+    }
+
+    async fn runtime(&self, netns: &Netns) -> Result<Arc<NetnsRuntime>, Error> {
+        if let Some(runtime) = self.runtimes.read().await.get(netns) {
+            return Ok(runtime.clone());
+        }
+
+        let mut runtimes = self.runtimes.write().await;
+
+        if let Some(runtime) = runtimes.get(netns) {
+            return Ok(runtime.clone());
+        }
+
+        let runtime = Arc::new(NetnsRuntime::spawn(netns.clone()).await?);
+        runtimes.insert(netns.clone(), runtime.clone());
+
+        Ok(runtime)
+    }
+
+    pub async fn list_routes(&self, netns: &Netns) -> Result<Vec<Route>, Error> {
+        let runtime = self.runtime(netns).await?;
+        let (tx, rx) = oneshot::channel();
+
+        runtime
+            .commands
+            .send(Command::List(tx))
+            .map_err(|_| Error::RuntimeGone(netns.clone()))?;
+
+        rx.await.map_err(|_| Error::RuntimeGone(netns.clone()))?
+    }
+
+    pub async fn add_route(&self, netns: &Netns, route: Route) -> Result<(), Error> {
+        let runtime = self.runtime(netns).await?;
+        let (tx, rx) = oneshot::channel();
+
+        runtime
+            .commands
+            .send(Command::Add(route, tx))
+            .map_err(|_| Error::RuntimeGone(netns.clone()))?;
+
+        rx.await.map_err(|_| Error::RuntimeGone(netns.clone()))?
+    }
+
+    pub async fn delete_route(&self, netns: &Netns, route: Route) -> Result<(), Error> {
+        let runtime = self.runtime(netns).await?;
+        let (tx, rx) = oneshot::channel();
+
+        runtime
+            .commands
+            .send(Command::Delete(route, tx))
+            .map_err(|_| Error::RuntimeGone(netns.clone()))?;
+
+        rx.await.map_err(|_| Error::RuntimeGone(netns.clone()))?
+    }
+
+    pub async fn watch_routes(
+        &self,
+        netns: &Netns,
+    ) -> Result<UnboundedReceiverStream<RouteEvent>, Error> {
+        let runtime = self.runtime(netns).await?;
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        runtime
+            .commands
+            .send(Command::Watch(tx))
+            .map_err(|_| Error::RuntimeGone(netns.clone()))?;
+
+        Ok(UnboundedReceiverStream::new(rx))
+    }
+
+    pub async fn list_addresses(&self, netns: &Netns) -> Result<Vec<Address>, Error> {
+        let runtime = self.runtime(netns).await?;
+        let (tx, rx) = oneshot::channel();
+
+        runtime
+            .commands
+            .send(Command::ListAddresses(tx))
+            .map_err(|_| Error::RuntimeGone(netns.clone()))?;
+
+        rx.await.map_err(|_| Error::RuntimeGone(netns.clone()))?
+    }
+
+    pub async fn list_links(&self, netns: &Netns) -> Result<Vec<Link>, Error> {
+        let runtime = self.runtime(netns).await?;
+        let (tx, rx) = oneshot::channel();
+
+        runtime
+            .commands
+            .send(Command::ListLinks(tx))
+            .map_err(|_| Error::RuntimeGone(netns.clone()))?;
+
+        rx.await.map_err(|_| Error::RuntimeGone(netns.clone()))?
+    }
+}